@@ -45,6 +45,12 @@ pub enum CpuFamily {
     Intel64,
     Arm32,
     Arm64,
+    Ppc,
+    Ppc64,
+    Mips,
+    Riscv32,
+    Riscv64,
+    Wasm32,
     Unknown,
 }
 
@@ -65,6 +71,13 @@ pub enum Arch {
     ArmV7m,
     ArmV7em,
     Arm64,
+    Arm64e,
+    Ppc,
+    Ppc64,
+    Mips,
+    Riscv32,
+    Riscv64,
+    Wasm32,
     #[doc(hidden)]
     __Max
 }
@@ -97,15 +110,29 @@ impl Arch {
         }
     }
 
-    /// Constructs an architecture from ELF flags
+    /// Constructs an architecture from ELF flags.
+    ///
+    /// `data` must be the full contents of the ELF file `elf` was parsed
+    /// from: for `EM_ARM`, distinguishing the actual ARM ISA version
+    /// requires reading the `Tag_CPU_arch` build attribute out of the
+    /// `.ARM.attributes` section, which `goblin`'s parsed headers reference
+    /// but do not themselves contain.
     #[cfg(feature = "with_objects")]
-    pub fn from_elf(machine: u16) -> Result<Arch> {
+    pub fn from_elf(elf: &goblin::elf::Elf, data: &[u8]) -> Result<Arch> {
         use goblin::elf::header::*;
-        Ok(match machine {
+        Ok(match elf.header.e_machine {
             EM_386 => Arch::X86,
             EM_X86_64 => Arch::X86_64,
-            // FIXME: This is incorrect! ARM information is located in the .ARM.attributes section
-            EM_ARM => Arch::ArmV7,
+            EM_ARM => from_elf_arm_attributes(elf, data).unwrap_or(Arch::ArmV7),
+            EM_AARCH64 => Arch::Arm64,
+            EM_PPC => Arch::Ppc,
+            EM_PPC64 => Arch::Ppc64,
+            EM_MIPS => Arch::Mips,
+            EM_RISCV => match elf.header.e_ident[EI_CLASS] {
+                ELFCLASS32 => Arch::Riscv32,
+                ELFCLASS64 => Arch::Riscv64,
+                _ => return Err(ErrorKind::Parse("unknown architecture").into()),
+            },
             _ => return Err(ErrorKind::Parse("unknown architecture").into()),
         })
     }
@@ -117,6 +144,7 @@ impl Arch {
             "x86" => X86,
             "x86_64" => X86_64,
             "arm64" => Arm64,
+            "arm64e" => Arm64e,
             "armv5" => ArmV5,
             "armv6" => ArmV6,
             "armv7" => ArmV7,
@@ -125,6 +153,12 @@ impl Arch {
             "armv7k" => ArmV7k,
             "armv7m" => ArmV7m,
             "armv7em" => ArmV7em,
+            "ppc" => Ppc,
+            "ppc64" => Ppc64,
+            "mips" => Mips,
+            "riscv32" => Riscv32,
+            "riscv64" => Riscv64,
+            "wasm32" => Wasm32,
             _ => {
                 return Err(ErrorKind::Parse("unknown architecture").into());
             }
@@ -138,8 +172,14 @@ impl Arch {
             Unknown | __Max => CpuFamily::Unknown,
             X86 => CpuFamily::Intel32,
             X86_64 => CpuFamily::Intel64,
-            Arm64 => CpuFamily::Arm64,
+            Arm64 | Arm64e => CpuFamily::Arm64,
             ArmV5 | ArmV6 | ArmV7 | ArmV7f | ArmV7s | ArmV7k | ArmV7m | ArmV7em => CpuFamily::Arm32,
+            Ppc => CpuFamily::Ppc,
+            Ppc64 => CpuFamily::Ppc64,
+            Mips => CpuFamily::Mips,
+            Riscv32 => CpuFamily::Riscv32,
+            Riscv64 => CpuFamily::Riscv64,
+            Wasm32 => CpuFamily::Wasm32,
         }
     }
 
@@ -148,8 +188,9 @@ impl Arch {
         use Arch::*;
         match *self {
             Unknown | __Max => None,
-            X86_64 | Arm64 => Some(8),
-            X86 | ArmV5 | ArmV6 | ArmV7 | ArmV7f | ArmV7s | ArmV7k | ArmV7m | ArmV7em => Some(4),
+            X86_64 | Arm64 | Arm64e | Ppc64 | Riscv64 => Some(8),
+            X86 | ArmV5 | ArmV6 | ArmV7 | ArmV7f | ArmV7s | ArmV7k | ArmV7m | ArmV7em | Ppc
+            | Mips | Riscv32 | Wasm32 => Some(4),
         }
     }
 
@@ -161,6 +202,7 @@ impl Arch {
             X86 => b"x86\0",
             X86_64 => b"x86_64\0",
             Arm64 => b"arm64\0",
+            Arm64e => b"arm64e\0",
             ArmV5 => b"armv5\0",
             ArmV6 => b"armv6\0",
             ArmV7 => b"armv7\0",
@@ -169,6 +211,12 @@ impl Arch {
             ArmV7k => b"armv7k\0",
             ArmV7m => b"armv7m\0",
             ArmV7em => b"armv7em\0",
+            Ppc => b"ppc\0",
+            Ppc64 => b"ppc64\0",
+            Mips => b"mips\0",
+            Riscv32 => b"riscv32\0",
+            Riscv64 => b"riscv64\0",
+            Wasm32 => b"wasm32\0",
         }).unwrap()
     }
 
@@ -179,6 +227,133 @@ impl Arch {
     }
 }
 
+/// Locates the `.ARM.attributes` section (if any) and parses its `Tag_CPU_arch`
+/// build attribute (ARM IHI 0045, "Build Attributes") to distinguish the
+/// actual ARMv5/v6/v7 ISA, since `EM_ARM` alone does not.
+#[cfg(feature = "with_objects")]
+fn from_elf_arm_attributes(elf: &goblin::elf::Elf, data: &[u8]) -> Option<Arch> {
+    let section = elf
+        .section_headers
+        .iter()
+        .find(|sh| elf.shdr_strtab.get_at(sh.sh_name) == Some(".ARM.attributes"))?;
+    let start = section.sh_offset as usize;
+    let end = start.checked_add(section.sh_size as usize)?;
+    parse_arm_tag_cpu_arch(data.get(start..end)?)
+}
+
+/// Minimal parser for the `aeabi` vendor subsection of an `.ARM.attributes`
+/// "Build Attributes" blob, just enough to pull out `Tag_CPU_arch` (tag 6).
+#[cfg(feature = "with_objects")]
+fn parse_arm_tag_cpu_arch(attributes: &[u8]) -> Option<Arch> {
+    // Byte 0 is the format-version, which must be 'A' ("Annotated").
+    if attributes.first() != Some(&b'A') {
+        return None;
+    }
+
+    let mut cursor = &attributes[1..];
+    while !cursor.is_empty() {
+        // Each subsection starts with a 4-byte (length-of-subsection,
+        // including this field) little-endian length, followed by a
+        // NUL-terminated vendor name.
+        let length = u32::from_le_bytes(cursor.get(0..4)?.try_into().ok()?) as usize;
+        if length < 4 || length > cursor.len() {
+            return None;
+        }
+        let subsection = &cursor[4..length];
+        let vendor_end = subsection.iter().position(|&b| b == 0)?;
+        if &subsection[..vendor_end] == b"aeabi" {
+            if let Some(arch) = parse_aeabi_subsection(&subsection[vendor_end + 1..]) {
+                return Some(arch);
+            }
+        }
+        cursor = &cursor[length..];
+    }
+
+    None
+}
+
+/// Walks the `(tag, size, contents)` sub-subsections of an `aeabi` vendor
+/// subsection looking for `Tag_File` (tag 1), whose contents is a sequence
+/// of `(attribute-tag, value)` pairs that includes `Tag_CPU_arch`.
+#[cfg(feature = "with_objects")]
+fn parse_aeabi_subsection(mut body: &[u8]) -> Option<Arch> {
+    while !body.is_empty() {
+        let sub_tag = *body.first()?;
+        let sub_length = u32::from_le_bytes(body.get(1..5)?.try_into().ok()?) as usize;
+        if sub_length < 5 || sub_length > body.len() {
+            return None;
+        }
+
+        if sub_tag == 1 {
+            let mut attrs = &body[5..sub_length];
+            while !attrs.is_empty() {
+                let (tag, rest) = read_uleb128(attrs)?;
+                if is_ntbs_tag(tag) {
+                    // e.g. `Tag_CPU_raw_name` (4) and `Tag_CPU_name` (5),
+                    // which GCC/Clang emit right before `Tag_CPU_arch` in
+                    // practically every ARM object: the value is a
+                    // NUL-terminated string, not a ULEB128 integer, so skip
+                    // past it rather than decoding it as one (which would
+                    // desync every tag read after it).
+                    let nul = rest.iter().position(|&b| b == 0)?;
+                    attrs = &rest[nul + 1..];
+                    continue;
+                }
+
+                let (value, rest) = read_uleb128(rest)?;
+                if tag == 6 {
+                    return tag_cpu_arch_to_arch(value);
+                }
+                attrs = rest;
+            }
+        }
+
+        body = &body[sub_length..];
+    }
+
+    None
+}
+
+/// Whether an `aeabi` `Tag_File` attribute-tag's value is a NUL-terminated
+/// string rather than a ULEB128 integer: per the ARM "Build Attributes"
+/// spec (ARM IHI 0045) this holds for odd-numbered tags, plus the even
+/// `Tag_CPU_raw_name` (4) exception.
+#[cfg(feature = "with_objects")]
+fn is_ntbs_tag(tag: u64) -> bool {
+    tag % 2 == 1 || tag == 4
+}
+
+/// Maps a `Tag_CPU_arch` value (ARM IHI 0045, table "CPU_arch") to our
+/// `Arch`. `EM_ARM` is always a 32-bit ELF machine type, so even the ARMv8-A
+/// entry (`AArch32` execution state) maps to a 32-bit `Arch` variant here;
+/// 64-bit AArch64 objects use `EM_AARCH64` instead and never reach this code.
+#[cfg(feature = "with_objects")]
+fn tag_cpu_arch_to_arch(value: u64) -> Option<Arch> {
+    Some(match value {
+        1 | 2 | 3 | 4 | 5 => Arch::ArmV5,        // v4, v4T, v5T, v5TE, v5TEJ
+        6 | 7 | 8 | 9 | 11 | 12 => Arch::ArmV6,  // v6, v6KZ, v6T2, v6K, v6-M, v6S-M
+        10 | 14 => Arch::ArmV7,                  // v7, v8-A (AArch32)
+        13 => Arch::ArmV7em,                     // v7E-M
+        _ => return None,
+    })
+}
+
+/// Reads a single unsigned LEB128 value, returning it along with the
+/// remaining bytes.
+#[cfg(feature = "with_objects")]
+fn read_uleb128(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut result = 0u64;
+    let mut shift = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        result |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some((result, &bytes[i + 1..]));
+        }
+        shift += 7;
+    }
+    None
+}
+
 impl fmt::Display for Arch {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.name())
@@ -232,6 +407,31 @@ impl Language {
     }
 }
 
+#[cfg(all(test, feature = "with_dwarf"))]
+mod language_tests {
+    use super::*;
+
+    #[test]
+    fn from_dwarf_lang_maps_known_c_and_cpp_variants() {
+        assert_eq!(Language::from_dwarf_lang(gimli::DW_LANG_C99), Some(Language::C));
+        assert_eq!(
+            Language::from_dwarf_lang(gimli::DW_LANG_C_plus_plus_14),
+            Some(Language::Cpp)
+        );
+    }
+
+    #[test]
+    fn from_dwarf_lang_maps_rust_and_swift() {
+        assert_eq!(Language::from_dwarf_lang(gimli::DW_LANG_Rust), Some(Language::Rust));
+        assert_eq!(Language::from_dwarf_lang(gimli::DW_LANG_Swift), Some(Language::Swift));
+    }
+
+    #[test]
+    fn from_dwarf_lang_returns_none_for_unmapped_tags() {
+        assert_eq!(Language::from_dwarf_lang(gimli::DW_LANG_Ada83), None);
+    }
+}
+
 impl fmt::Display for Language {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", match *self {
@@ -254,3 +454,60 @@ pub enum ObjectKind {
     MachO,
     Elf,
 }
+
+#[cfg(all(test, feature = "with_objects"))]
+mod arm_attributes_tests {
+    use super::*;
+
+    fn uleb128(mut value: u64, out: &mut Vec<u8>) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    /// Builds a realistic `.ARM.attributes` section blob containing a single
+    /// `aeabi` vendor subsection with one `Tag_File` sub-subsection, whose
+    /// attribute stream is `Tag_CPU_name` (5, a NUL-terminated string, as
+    /// GCC/Clang emit right before `Tag_CPU_arch`) followed by `Tag_CPU_arch`
+    /// (6, a ULEB128 value).
+    fn build_arm_attributes(cpu_name: &str, tag_cpu_arch_value: u64) -> Vec<u8> {
+        let mut attrs = Vec::new();
+        attrs.push(5u8); // Tag_CPU_name
+        attrs.extend_from_slice(cpu_name.as_bytes());
+        attrs.push(0); // NUL terminator
+        uleb128(6, &mut attrs); // Tag_CPU_arch
+        uleb128(tag_cpu_arch_value, &mut attrs);
+
+        let mut file_subsection = Vec::new();
+        file_subsection.push(1u8); // Tag_File
+        file_subsection.extend_from_slice(&((5 + attrs.len()) as u32).to_le_bytes());
+        file_subsection.extend_from_slice(&attrs);
+
+        let mut vendor_subsection = Vec::new();
+        vendor_subsection.extend_from_slice(b"aeabi\0");
+        vendor_subsection.extend_from_slice(&file_subsection);
+
+        let mut attributes = Vec::new();
+        attributes.push(b'A');
+        attributes.extend_from_slice(&((4 + vendor_subsection.len()) as u32).to_le_bytes());
+        attributes.extend_from_slice(&vendor_subsection);
+        attributes
+    }
+
+    #[test]
+    fn parses_tag_cpu_arch_past_a_preceding_string_tag() {
+        let attributes = build_arm_attributes("Cortex-A8", 10);
+        assert_eq!(parse_arm_tag_cpu_arch(&attributes), Some(Arch::ArmV7));
+    }
+
+    #[test]
+    fn read_uleb128_decodes_multi_byte_values() {
+        assert_eq!(read_uleb128(&[0xe5, 0x8e, 0x26]), Some((624_485, &[][..])));
+    }
+}
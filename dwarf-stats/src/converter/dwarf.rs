@@ -1,29 +1,103 @@
 use std::collections::hash_map::Entry;
-use std::collections::{btree_map, BTreeMap, HashMap};
+use std::collections::{btree_map, BTreeMap, HashMap, HashSet};
 use std::mem;
 use std::num::NonZeroU64;
 use std::ops::Bound;
 
 use gimli::{
-    constants, DebuggingInformationEntry, Dwarf, IncompleteLineProgram, LineProgramHeader, Unit,
+    constants, AttributeValue, DebugInfoOffset, DebuggingInformationEntry, Dwarf,
+    IncompleteLineProgram, LineProgramHeader, Unit,
 };
 
+use symbolic_common::types::Language;
+
 use super::*;
 
 type Result<T, E = gimli::Error> = std::result::Result<T, E>;
 
+/// Supplies the split-DWARF (`.dwo`/`.dwp`) companion data for a skeleton
+/// unit (one carrying `DW_AT_(GNU_)dwo_name`/`DW_AT_(GNU_)dwo_id`).
+///
+/// Implementors typically resolve `dwo_name` relative to the executable's
+/// directory, or look `dwo_id` up in a `.dwp` package, and parse the result
+/// into a [`gimli::Dwarf`]. Returning `None` causes the skeleton unit to be
+/// skipped, mirroring addr2line's `builtin_split_dwarf_loader` fallback.
+pub trait SplitDwarfLoader<R: gimli::Reader> {
+    fn load_dwo(&mut self, dwo_name: &str, dwo_id: gimli::DwoId) -> Option<Dwarf<R>>;
+}
+
 impl Converter {
-    pub fn process_dwarf<R: gimli::Reader>(&mut self, dwarf: &Dwarf<R>) -> Result<()> {
+    pub fn process_dwarf<R: gimli::Reader, L: SplitDwarfLoader<R>>(
+        &mut self,
+        dwarf: &Dwarf<R>,
+        loader: &mut L,
+    ) -> Result<()> {
         let mut reusable_cache = ReusableCaches::default();
         // Iterate over the compilation units.
         let mut iter = dwarf.units();
         while let Some(header) = iter.next()? {
             let unit = dwarf.unit(header)?;
-            self.process_dwarf_cu(&mut reusable_cache, dwarf, &unit)?;
+            if !self.process_split_unit(&mut reusable_cache, dwarf, &unit, loader)? {
+                self.process_dwarf_cu(&mut reusable_cache, dwarf, &unit)?;
+            }
         }
         Ok(())
     }
 
+    /// Detects whether `skeleton` is a split-DWARF skeleton unit and, if so,
+    /// loads its `.dwo`/`.dwp` companion via `loader` and processes that
+    /// instead. Returns `true` if `skeleton` was a skeleton unit at all
+    /// (whether or not the companion could actually be loaded), so the
+    /// caller never also processes the (DIE-less) skeleton itself.
+    fn process_split_unit<R: gimli::Reader, L: SplitDwarfLoader<R>>(
+        &mut self,
+        reusable_cache: &mut ReusableCaches,
+        dwarf: &Dwarf<R>,
+        skeleton: &Unit<R>,
+        loader: &mut L,
+    ) -> Result<bool> {
+        let info = match split_dwarf_info(dwarf, skeleton)? {
+            Some(info) => info,
+            None => return Ok(false),
+        };
+
+        let mut dwo_dwarf = match loader.load_dwo(&info.dwo_name, info.dwo_id) {
+            Some(dwo_dwarf) => dwo_dwarf,
+            None => return Ok(true),
+        };
+        // `.debug_addr` physically lives in the skeleton's object, not in the
+        // `.dwo`: `DW_FORM_addrx` operands in the split unit (including the
+        // low PC used to recompose ranges and line-table addresses) are
+        // indices into it, based at the skeleton's `addr_base`.
+        dwo_dwarf.debug_addr = dwarf.debug_addr.clone();
+
+        // A `.dwp` package bundles many CUs; make sure we pick out the one
+        // the skeleton actually asked for rather than assuming the loader
+        // already filtered it down to a single unit.
+        let mut headers = dwo_dwarf.units();
+        let mut dwo_unit = loop {
+            let header = match headers.next()? {
+                Some(header) => header,
+                None => return Ok(true),
+            };
+            let unit = dwo_dwarf.unit(header)?;
+            if unit_dwo_id(&unit)? == Some(info.dwo_id) {
+                break unit;
+            }
+        };
+        dwo_unit.addr_base = skeleton.addr_base;
+        dwo_unit.low_pc = skeleton.low_pc;
+        // `DW_AT_stmt_list`/the line-number program lives in the skeleton
+        // object, not the `.dwo` (whose root DIE normally carries no
+        // `DW_AT_stmt_list` at all): without this, `dwo_unit.line_program`
+        // would be `None` and `process_dwarf_cu` would bail out immediately
+        // for every split-DWARF CU.
+        dwo_unit.line_program = skeleton.line_program.clone();
+
+        self.process_dwarf_cu(reusable_cache, &dwo_dwarf, &dwo_unit)?;
+        Ok(true)
+    }
+
     fn process_dwarf_cu<R: gimli::Reader>(
         &mut self,
         reusable_cache: &mut ReusableCaches,
@@ -38,6 +112,7 @@ impl Converter {
         let mut cu_cache =
             PerCuCache::new(reusable_cache, dwarf, unit, line_program.header().clone());
         let sequences = parse_line_program(line_program)?;
+        let language = cu_language(unit)?;
 
         // TODO: figure out if we actually need to keep "sequences" separate?
         let mut line_program_ranges = BTreeMap::new();
@@ -47,14 +122,20 @@ impl Converter {
 
                 line_program_ranges.insert(
                     row.address as u32,
-                    SourceLocation {
+                    Some(SourceLocation {
                         file_idx,
                         line: row.line,
                         function_idx: u32::MAX,
                         inlined_into_idx: None,
-                    },
+                    }),
                 );
             }
+            // Mark the end of this sequence as explicitly unmapped, so a
+            // lookup for an address in the gap after it (or past the end of
+            // the last sequence entirely) doesn't incorrectly fall back to
+            // the preceding row. `or_insert` keeps a real row that another
+            // (contiguous) sequence happens to start exactly here.
+            line_program_ranges.entry(seq.end as u32).or_insert(None);
         }
 
         // Iterate over the Debugging Information Entries (DIEs) in the unit.
@@ -74,13 +155,11 @@ impl Converter {
                 None => 0,
             };
             let caller_line = caller_info.1.unwrap_or(0) as u32;
+            let function_idx = self.resolve_function_idx(dwarf, unit, entry, language)?;
 
             let mut ranges = dwarf.die_ranges(unit, entry)?;
             while let Some(range) = ranges.next()? {
                 if is_inlined_subroutine {
-                    // TODO: insert function info
-                    let function_idx = u32::MAX;
-
                     for callee_source_location in sub_ranges(&mut line_program_ranges, &range) {
                         let mut caller_source_location = callee_source_location.clone();
                         caller_source_location.file_idx = caller_file;
@@ -91,9 +170,6 @@ impl Converter {
                         callee_source_location.function_idx = function_idx;
                     }
                 } else {
-                    // TODO: insert function info
-                    let function_idx = u32::MAX;
-
                     for source_location in sub_ranges(&mut line_program_ranges, &range) {
                         source_location.function_idx = function_idx;
                     }
@@ -102,15 +178,25 @@ impl Converter {
         }
 
         for (addr, source_location) in line_program_ranges {
-            let source_location_idx = self.insert_source_location(source_location);
+            let value = source_location.map(|source_location| self.insert_source_location(source_location));
 
             match self.ranges.entry(addr) {
                 btree_map::Entry::Vacant(entry) => {
-                    entry.insert(source_location_idx);
+                    entry.insert(value);
                 }
-                btree_map::Entry::Occupied(_) => {
-                    // TODO: figure out what to do in this case? Why does it happen?
-                    // panic!("entry for line program row {:?} should not exist yet!", row);
+                btree_map::Entry::Occupied(mut entry) => {
+                    // `line_program_ranges` is local to this CU, so within a
+                    // single call it can't collide with itself. This arm is
+                    // reached when a *different* CU maps the same global
+                    // address, e.g. via an inline asm block or a
+                    // linker-identical COMDAT function folded into both, or
+                    // starts a sequence right where an earlier CU's ended.
+                    // An earlier CU's end-of-sequence marker gives way to a
+                    // real row here; otherwise the first CU we processed
+                    // wins and later ones are dropped.
+                    if entry.get().is_none() {
+                        entry.insert(value);
+                    }
                 }
             }
         }
@@ -121,10 +207,144 @@ impl Converter {
     fn insert_source_location(&mut self, source_location: SourceLocation) -> u32 {
         self.source_locations.insert_full(source_location).0 as u32
     }
+
+    /// Resolves (and interns) the [`Function`] that a `DW_TAG_subprogram` or
+    /// `DW_TAG_inlined_subroutine` DIE refers to, returning its index.
+    fn resolve_function_idx<R: gimli::Reader>(
+        &mut self,
+        dwarf: &Dwarf<R>,
+        unit: &Unit<R>,
+        entry: &DebuggingInformationEntry<R>,
+        language: Language,
+    ) -> Result<u32> {
+        let name = resolve_function_name(dwarf, unit, entry, &mut HashSet::new())?;
+        let name_idx = match name {
+            Some(name) => Some(self.strings.insert_full(name).0 as u32),
+            None => None,
+        };
+        // `dwarf.attr_address` (rather than matching `AttributeValue::Addr`
+        // directly) also resolves the indexed `DW_FORM_addrx` encoding that
+        // split-DWARF `.dwo` units commonly use for `DW_AT_low_pc`.
+        let entry_pc = match entry.attr_value(constants::DW_AT_low_pc)? {
+            Some(value) => dwarf.attr_address(unit, value)?,
+            None => None,
+        };
+
+        Ok(self
+            .functions
+            .insert_full(Function {
+                name_idx,
+                entry_pc,
+                language,
+            })
+            .0 as u32)
+    }
+}
+
+/// Reads `DW_AT_language` off `unit`'s root (`DW_TAG_compile_unit`) DIE and
+/// maps it to a [`Language`], defaulting to [`Language::Unknown`] when the
+/// attribute is absent or not one we recognize.
+fn cu_language<R: gimli::Reader>(unit: &Unit<R>) -> Result<Language> {
+    let root = match unit.entries().next_dfs()? {
+        Some((_, entry)) => entry,
+        None => return Ok(Language::Unknown),
+    };
+
+    Ok(match root.attr_value(constants::DW_AT_language)? {
+        Some(AttributeValue::Language(lang)) => {
+            Language::from_dwarf_lang(lang).unwrap_or(Language::Unknown)
+        }
+        _ => Language::Unknown,
+    })
+}
+
+/// Resolves the name of a subprogram/inlined-subroutine DIE, following
+/// `DW_AT_abstract_origin` and `DW_AT_specification` chains the way
+/// addr2line's `Function::name` does: try `DW_AT_name`, then
+/// `DW_AT_linkage_name` on `entry` itself, and if neither is present recurse
+/// into the referenced DIE. References can cross compilation units via
+/// `DW_FORM_ref_addr`, which is why this takes `dwarf` rather than just
+/// `unit`. `visited` guards against origin/specification cycles.
+fn resolve_function_name<R: gimli::Reader>(
+    dwarf: &Dwarf<R>,
+    unit: &Unit<R>,
+    entry: &DebuggingInformationEntry<R>,
+    visited: &mut HashSet<DebugInfoOffset<R::Offset>>,
+) -> Result<Option<String>> {
+    if let Some(name) = direct_name(dwarf, unit, entry)? {
+        return Ok(Some(name));
+    }
+
+    for reference_attr in [constants::DW_AT_abstract_origin, constants::DW_AT_specification] {
+        let value = match entry.attr_value(reference_attr)? {
+            Some(value) => value,
+            None => continue,
+        };
+
+        match value {
+            AttributeValue::UnitRef(offset) => {
+                // Intra-unit references are just as prone to
+                // origin/specification cycles as cross-unit ones, so they
+                // need to go through the same `visited` guard: resolve this
+                // offset to its global `.debug_info` position and make sure
+                // we haven't already followed it.
+                if let gimli::UnitSectionOffset::DebugInfoOffset(debug_info_offset) =
+                    offset.to_unit_section_offset(unit)
+                {
+                    if !visited.insert(debug_info_offset) {
+                        continue; // Already followed this reference; avoid cycles.
+                    }
+                }
+
+                let ref_entry = unit.entry(offset)?;
+                if let Some(name) = resolve_function_name(dwarf, unit, &ref_entry, visited)? {
+                    return Ok(Some(name));
+                }
+            }
+            AttributeValue::DebugInfoRef(global_offset) => {
+                if !visited.insert(global_offset) {
+                    continue; // Already followed this reference; avoid cycles.
+                }
+
+                let mut headers = dwarf.units();
+                while let Some(header) = headers.next()? {
+                    let offset = match global_offset.to_unit_offset(&header) {
+                        Some(offset) => offset,
+                        None => continue,
+                    };
+                    let ref_unit = dwarf.unit(header)?;
+                    let ref_entry = ref_unit.entry(offset)?;
+                    if let Some(name) = resolve_function_name(dwarf, &ref_unit, &ref_entry, visited)? {
+                        return Ok(Some(name));
+                    }
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// Tries `DW_AT_name`, then `DW_AT_linkage_name`, directly on `entry`.
+fn direct_name<R: gimli::Reader>(
+    dwarf: &Dwarf<R>,
+    unit: &Unit<R>,
+    entry: &DebuggingInformationEntry<R>,
+) -> Result<Option<String>> {
+    for name_attr in [constants::DW_AT_name, constants::DW_AT_linkage_name] {
+        if let Some(attr) = entry.attr_value(name_attr)? {
+            if let Ok(name) = dwarf.attr_string(unit, attr) {
+                return Ok(Some(name.to_string_lossy()?.into_owned()));
+            }
+        }
+    }
+    Ok(None)
 }
 
 fn sub_ranges<'a>(
-    ranges: &'a mut BTreeMap<u32, SourceLocation>,
+    ranges: &'a mut BTreeMap<u32, Option<SourceLocation>>,
     range: &gimli::Range,
 ) -> impl Iterator<Item = &'a mut SourceLocation> {
     let first_after = ranges.range(range.end as u32..).next();
@@ -134,7 +354,11 @@ fn sub_ranges<'a>(
         Bound::Unbounded
     };
     let lower_bound = Bound::Included(range.begin as u32);
-    ranges.range_mut((lower_bound, upper_bound)).map(|(_, v)| v)
+    // Tombstone (unmapped) entries have no `SourceLocation` to annotate with
+    // function info, so they're simply skipped here.
+    ranges
+        .range_mut((lower_bound, upper_bound))
+        .filter_map(|(_, v)| v.as_mut())
 }
 
 #[derive(Debug, Default)]
@@ -217,6 +441,74 @@ impl<'dwarf, R: gimli::Reader> PerCuCache<'dwarf, R> {
     }
 }
 
+/// The `DW_AT_(GNU_)dwo_name`/`DW_AT_(GNU_)dwo_id` pair read off a CU's root
+/// DIE that marks it as a split-DWARF skeleton unit.
+struct SplitDwarfInfo {
+    dwo_name: String,
+    dwo_id: gimli::DwoId,
+}
+
+/// Returns [`SplitDwarfInfo`] if `unit`'s root DIE carries the attributes
+/// that mark it as a split-DWARF skeleton unit (`-gsplit-dwarf`), or `None`
+/// for an ordinary, self-contained unit.
+fn split_dwarf_info<R: gimli::Reader>(
+    dwarf: &Dwarf<R>,
+    unit: &Unit<R>,
+) -> Result<Option<SplitDwarfInfo>> {
+    let root = match unit.entries().next_dfs()? {
+        Some((_, entry)) => entry,
+        None => return Ok(None),
+    };
+
+    let dwo_name_attr = match root.attr_value(constants::DW_AT_GNU_dwo_name)? {
+        Some(value) => Some(value),
+        None => root.attr_value(constants::DW_AT_dwo_name)?,
+    };
+    let dwo_name = match dwo_name_attr {
+        Some(value) => dwarf
+            .attr_string(unit, value)?
+            .to_string_lossy()?
+            .into_owned(),
+        None => return Ok(None),
+    };
+
+    let dwo_id_attr = match root.attr_value(constants::DW_AT_GNU_dwo_id)? {
+        Some(value) => Some(value),
+        None => root.attr_value(constants::DW_AT_dwo_id)?,
+    };
+    let dwo_id = match dwo_id_attr {
+        Some(AttributeValue::DwoId(id)) => id,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(SplitDwarfInfo { dwo_name, dwo_id }))
+}
+
+/// Returns the `dwo_id` a `.dwo`/`.dwp` compilation unit identifies itself
+/// with, if any: either embedded directly in a DWARF5 split/skeleton unit
+/// header, or (the GNU `-gsplit-dwarf` extension predating DWARF5) carried
+/// as a `DW_AT_(GNU_)dwo_id` attribute on the unit's own root DIE.
+fn unit_dwo_id<R: gimli::Reader>(unit: &Unit<R>) -> Result<Option<gimli::DwoId>> {
+    if let gimli::UnitType::Skeleton(dwo_id) | gimli::UnitType::SplitCompilation(dwo_id) =
+        unit.header.type_()
+    {
+        return Ok(Some(dwo_id));
+    }
+
+    let root = match unit.entries().next_dfs()? {
+        Some((_, entry)) => entry,
+        None => return Ok(None),
+    };
+    let dwo_id_attr = match root.attr_value(constants::DW_AT_GNU_dwo_id)? {
+        Some(value) => Some(value),
+        None => root.attr_value(constants::DW_AT_dwo_id)?,
+    };
+    Ok(match dwo_id_attr {
+        Some(AttributeValue::DwoId(id)) => Some(id),
+        _ => None,
+    })
+}
+
 fn find_caller_info<R: gimli::Reader>(
     entry: &DebuggingInformationEntry<R>,
 ) -> Result<(Option<u64>, Option<u64>)> {
@@ -366,8 +658,15 @@ mod tests {
     #[test]
     fn work_on_dwarf() -> Result<()> {
         with_loaded_dwarf("tests/fixtures/two_inlined.debug".as_ref(), |dwarf| {
+            struct NoSplitDwarf;
+            impl<R: gimli::Reader> SplitDwarfLoader<R> for NoSplitDwarf {
+                fn load_dwo(&mut self, _dwo_name: &str, _dwo_id: gimli::DwoId) -> Option<Dwarf<R>> {
+                    None
+                }
+            }
+
             let mut converter = Converter::new();
-            converter.process_dwarf(dwarf)?;
+            converter.process_dwarf(dwarf, &mut NoSplitDwarf)?;
 
             dbg!(&converter);
 
@@ -385,4 +684,247 @@ mod tests {
             Ok(())
         })
     }
+
+    // `work_on_dwarf` above exercises `SplitDwarfLoader` end-to-end against a
+    // (self-contained, non-split) fixture, where `load_dwo` is never called.
+    // This only checks that a loader returning `None` for a requested `.dwo`
+    // is a legal implementation of the trait; it does not run
+    // `process_split_unit`, which skips the skeleton unit in that case.
+    #[test]
+    fn split_dwarf_loader_none_is_a_valid_response() {
+        type Reader<'a> = gimli::EndianSlice<'a, gimli::RunTimeEndian>;
+
+        struct AlwaysMissing;
+        impl<R: gimli::Reader> SplitDwarfLoader<R> for AlwaysMissing {
+            fn load_dwo(&mut self, _dwo_name: &str, _dwo_id: gimli::DwoId) -> Option<Dwarf<R>> {
+                None
+            }
+        }
+
+        let mut loader = AlwaysMissing;
+        let result: Option<Dwarf<Reader>> =
+            loader.load_dwo("foo.dwo", gimli::DwoId(0x1234_5678_9abc_def0));
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn resolve_function_name_terminates_on_self_referencing_abstract_origin() {
+        // Hand-built single-unit CU containing one `DW_TAG_subprogram` DIE
+        // whose `DW_AT_abstract_origin` (DW_FORM_ref4) points back at itself.
+        // Before the `UnitRef` cycle guard, this made `resolve_function_name`
+        // recurse into the same DIE forever.
+        let abbrev: &[u8] = &[
+            1, 0x11, 1, 0, 0, // code 1: DW_TAG_compile_unit, has_children, terminator
+            2, 0x2e, 0, 0x31, 0x13, 0, 0, // code 2: DW_TAG_subprogram, DW_AT_abstract_origin/ref4
+            0, // abbreviation table terminator
+        ];
+
+        let mut info = vec![0u8; 4]; // unit_length, patched in below
+        info.extend_from_slice(&4u16.to_le_bytes()); // version
+        info.extend_from_slice(&0u32.to_le_bytes()); // debug_abbrev_offset
+        info.push(8); // address_size
+        info.push(1); // root DIE: abbrev code 1 (DW_TAG_compile_unit)
+        let child_offset = info.len() as u32;
+        info.push(2); // child DIE: abbrev code 2 (DW_TAG_subprogram)
+        info.extend_from_slice(&child_offset.to_le_bytes()); // abstract_origin -> itself
+        info.push(0); // end of root DIE's children
+        let unit_length = (info.len() - 4) as u32;
+        info[0..4].copy_from_slice(&unit_length.to_le_bytes());
+
+        let endian = gimli::LittleEndian;
+        let dwarf = gimli::Dwarf::load::<_, gimli::Error>(|id| {
+            Ok(match id {
+                gimli::SectionId::DebugInfo => gimli::EndianSlice::new(&info, endian),
+                gimli::SectionId::DebugAbbrev => gimli::EndianSlice::new(abbrev, endian),
+                _ => gimli::EndianSlice::new(&[], endian),
+            })
+        })
+        .unwrap();
+
+        let header = dwarf.units().next().unwrap().unwrap();
+        let unit = dwarf.unit(header).unwrap();
+        let child = unit
+            .entry(gimli::UnitOffset(child_offset as usize))
+            .unwrap();
+
+        let mut visited = HashSet::new();
+        let name = resolve_function_name(&dwarf, &unit, &child, &mut visited).unwrap();
+        assert_eq!(name, None);
+    }
+
+    #[test]
+    fn process_dwarf_propagates_skeleton_line_program_to_dwo_unit() {
+        // Exercises the split-DWARF path end-to-end: a skeleton CU pointing
+        // at a `.dwo` via `DW_AT_GNU_dwo_name`/`DW_AT_GNU_dwo_id`, whose own
+        // root DIE carries no `DW_AT_stmt_list` at all, as real `.dwo`
+        // objects don't. Before the skeleton's line program was propagated
+        // onto `dwo_unit`, `process_dwarf_cu` bailed out immediately (no
+        // line program) and the whole CU went unrecorded. This also covers
+        // picking the right CU out of a loader that doesn't pre-filter to a
+        // single unit, by giving the `.dwo` a `dwo_id` and matching on it.
+        let endian = gimli::LittleEndian;
+        let dwo_id: u64 = 0xdead_beef_cafe_babe;
+
+        // DW_AT_GNU_dwo_name, DW_AT_GNU_dwo_id as ULEB128.
+        const DW_AT_GNU_DWO_NAME: [u8; 2] = [0xb0, 0x42];
+        const DW_AT_GNU_DWO_ID: [u8; 2] = [0xb1, 0x42];
+
+        // --- `.debug_line`: a sequence with two rows (0x1000 -> line 10,
+        // 0x1010 -> line 20), ending at 0x1020.
+        let mut line = vec![0u8; 4]; // unit_length, patched below
+        line.extend_from_slice(&4u16.to_le_bytes()); // version
+        let header_length_pos = line.len();
+        line.extend_from_slice(&[0u8; 4]); // header_length, patched below
+        line.push(1); // minimum_instruction_length
+        line.push(1); // maximum_operations_per_instruction
+        line.push(1); // default_is_stmt
+        line.push(0xfb); // line_base = -5
+        line.push(14); // line_range
+        line.push(13); // opcode_base
+        line.extend_from_slice(&[0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1]); // standard_opcode_lengths
+        line.push(0); // include_directories terminator (none used)
+        line.extend_from_slice(b"test.c\0"); // file_names[1].name
+        line.push(0); // directory_index
+        line.push(0); // mtime
+        line.push(0); // length
+        line.push(0); // file_names terminator
+        let program_start = line.len();
+        let header_length = (program_start - header_length_pos - 4) as u32;
+        line[header_length_pos..header_length_pos + 4].copy_from_slice(&header_length.to_le_bytes());
+
+        line.push(0); // DW_LNE_set_address
+        line.push(9);
+        line.push(2);
+        line.extend_from_slice(&0x1000u64.to_le_bytes());
+        line.push(3); // DW_LNS_advance_line +9 (line 1 -> 10)
+        line.push(9);
+        line.push(1); // DW_LNS_copy: emits row (0x1000, line 10)
+        line.push(2); // DW_LNS_advance_pc +0x10
+        line.push(0x10);
+        line.push(3); // DW_LNS_advance_line +10 (line 10 -> 20)
+        line.push(10);
+        line.push(1); // DW_LNS_copy: emits row (0x1010, line 20)
+        line.push(2); // DW_LNS_advance_pc +0x10
+        line.push(0x10);
+        line.push(0); // DW_LNE_end_sequence: closes the sequence at 0x1020
+        line.push(1);
+        line.push(1);
+
+        let line_unit_length = (line.len() - 4) as u32;
+        line[0..4].copy_from_slice(&line_unit_length.to_le_bytes());
+
+        // --- skeleton `.debug_abbrev` + `.debug_info` ---
+        let mut skeleton_abbrev = vec![1, 0x11, 0]; // code 1: DW_TAG_compile_unit, no children
+        skeleton_abbrev.extend_from_slice(&DW_AT_GNU_DWO_NAME);
+        skeleton_abbrev.push(0x08); // DW_FORM_string
+        skeleton_abbrev.extend_from_slice(&DW_AT_GNU_DWO_ID);
+        skeleton_abbrev.push(0x07); // DW_FORM_data8
+        skeleton_abbrev.push(0x10); // DW_AT_stmt_list
+        skeleton_abbrev.push(0x17); // DW_FORM_sec_offset
+        skeleton_abbrev.extend_from_slice(&[0, 0]); // attribute list terminator
+        skeleton_abbrev.push(0); // abbreviation table terminator
+
+        let mut skeleton_info = vec![0u8; 4]; // unit_length, patched below
+        skeleton_info.extend_from_slice(&4u16.to_le_bytes()); // version
+        skeleton_info.extend_from_slice(&0u32.to_le_bytes()); // debug_abbrev_offset
+        skeleton_info.push(8); // address_size
+        skeleton_info.push(1); // abbrev code 1
+        skeleton_info.extend_from_slice(b"test.dwo\0"); // DW_AT_GNU_dwo_name
+        skeleton_info.extend_from_slice(&dwo_id.to_le_bytes()); // DW_AT_GNU_dwo_id
+        skeleton_info.extend_from_slice(&0u32.to_le_bytes()); // DW_AT_stmt_list -> .debug_line offset 0
+        let skeleton_unit_length = (skeleton_info.len() - 4) as u32;
+        skeleton_info[0..4].copy_from_slice(&skeleton_unit_length.to_le_bytes());
+
+        // --- `.dwo`'s own minimal `.debug_abbrev` + `.debug_info`: just
+        // enough for `Dwarf::unit` to capture a matching `dwo_id`. No
+        // `DW_AT_stmt_list` here, matching how real `.dwo` CUs look.
+        let mut dwo_abbrev = vec![1, 0x11, 0]; // code 1: DW_TAG_compile_unit, no children
+        dwo_abbrev.extend_from_slice(&DW_AT_GNU_DWO_ID);
+        dwo_abbrev.push(0x07); // DW_FORM_data8
+        dwo_abbrev.extend_from_slice(&[0, 0]);
+        dwo_abbrev.push(0);
+
+        let mut dwo_info = vec![0u8; 4]; // unit_length, patched below
+        dwo_info.extend_from_slice(&4u16.to_le_bytes()); // version
+        dwo_info.extend_from_slice(&0u32.to_le_bytes()); // debug_abbrev_offset
+        dwo_info.push(8); // address_size
+        dwo_info.push(1); // abbrev code 1
+        dwo_info.extend_from_slice(&dwo_id.to_le_bytes()); // DW_AT_GNU_dwo_id
+        let dwo_unit_length = (dwo_info.len() - 4) as u32;
+        dwo_info[0..4].copy_from_slice(&dwo_unit_length.to_le_bytes());
+
+        let skeleton_dwarf = gimli::Dwarf::load::<_, gimli::Error>(|id| {
+            Ok(match id {
+                gimli::SectionId::DebugInfo => gimli::EndianSlice::new(&skeleton_info, endian),
+                gimli::SectionId::DebugAbbrev => gimli::EndianSlice::new(&skeleton_abbrev, endian),
+                gimli::SectionId::DebugLine => gimli::EndianSlice::new(&line, endian),
+                _ => gimli::EndianSlice::new(&[], endian),
+            })
+        })
+        .unwrap();
+
+        let dwo_dwarf = gimli::Dwarf::load::<_, gimli::Error>(|id| {
+            Ok(match id {
+                gimli::SectionId::DebugInfo => gimli::EndianSlice::new(&dwo_info, endian),
+                gimli::SectionId::DebugAbbrev => gimli::EndianSlice::new(&dwo_abbrev, endian),
+                _ => gimli::EndianSlice::new(&[], endian),
+            })
+        })
+        .unwrap();
+
+        struct OneShotLoader<R: gimli::Reader>(Option<Dwarf<R>>);
+        impl<R: gimli::Reader> SplitDwarfLoader<R> for OneShotLoader<R> {
+            fn load_dwo(&mut self, _dwo_name: &str, _dwo_id: gimli::DwoId) -> Option<Dwarf<R>> {
+                self.0.take()
+            }
+        }
+
+        let mut converter = Converter::new();
+        let mut loader = OneShotLoader(Some(dwo_dwarf));
+        converter.process_dwarf(&skeleton_dwarf, &mut loader).unwrap();
+
+        let mut frames = converter.lookup(0x1005);
+        let loc = frames.next().expect("address inside the first row must resolve");
+        assert_eq!(loc.line(), 10);
+        assert_eq!(loc.path_name(), "test.c");
+
+        let mut frames = converter.lookup(0x1015);
+        let loc = frames.next().expect("address inside the second row must resolve");
+        assert_eq!(loc.line(), 20);
+
+        // The end-of-sequence tombstone at 0x1020 must not fall back to the
+        // preceding row.
+        assert!(converter.lookup(0x1020).next().is_none());
+    }
+
+    #[test]
+    fn cu_language_reads_dw_at_language_off_the_root_die() {
+        let abbrev: &[u8] = &[
+            1, 0x11, 0, 0x13, 0x0f, 0, 0, // code 1: DW_TAG_compile_unit, DW_AT_language/DW_FORM_udata
+            0, // abbreviation table terminator
+        ];
+
+        let mut info = vec![0u8; 4]; // unit_length, patched below
+        info.extend_from_slice(&4u16.to_le_bytes()); // version
+        info.extend_from_slice(&0u32.to_le_bytes()); // debug_abbrev_offset
+        info.push(8); // address_size
+        info.push(1); // root DIE: abbrev code 1
+        info.push(0x1c); // DW_AT_language = DW_LANG_Rust
+        let unit_length = (info.len() - 4) as u32;
+        info[0..4].copy_from_slice(&unit_length.to_le_bytes());
+
+        let endian = gimli::LittleEndian;
+        let dwarf = gimli::Dwarf::load::<_, gimli::Error>(|id| {
+            Ok(match id {
+                gimli::SectionId::DebugInfo => gimli::EndianSlice::new(&info, endian),
+                gimli::SectionId::DebugAbbrev => gimli::EndianSlice::new(abbrev, endian),
+                _ => gimli::EndianSlice::new(&[], endian),
+            })
+        })
+        .unwrap();
+
+        let header = dwarf.units().next().unwrap().unwrap();
+        let unit = dwarf.unit(header).unwrap();
+        assert_eq!(cu_language(&unit).unwrap(), Language::Rust);
+    }
 }
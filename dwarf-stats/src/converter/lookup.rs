@@ -0,0 +1,324 @@
+//! Point and range lookup of resolved source locations.
+
+use std::borrow::Cow;
+use std::collections::btree_map;
+
+use symbolic_common::types::Language;
+use symbolic_demangle::{demangle, DemangleOptions};
+
+use super::{Converter, File, Function};
+
+/// A resolved source location, borrowed from a [`Converter`].
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedLocation<'a> {
+    converter: &'a Converter,
+    source_location: &'a super::SourceLocation,
+}
+
+impl<'a> ResolvedLocation<'a> {
+    /// The directory containing the source file, if recorded.
+    pub fn directory(&self) -> Option<&'a str> {
+        let directory_idx = self.file()?.directory_idx?;
+        self.converter
+            .strings
+            .get_index(directory_idx as usize)
+            .map(String::as_str)
+    }
+
+    /// The name of the source file.
+    pub fn path_name(&self) -> &'a str {
+        self.file()
+            .and_then(|file| self.converter.strings.get_index(file.path_name_idx as usize))
+            .map(String::as_str)
+            .unwrap_or("")
+    }
+
+    /// The 1-based source line, or `0` if unknown.
+    pub fn line(&self) -> u32 {
+        self.source_location.line
+    }
+
+    /// The function this location belongs to, if one was resolved.
+    pub fn function(&self) -> Option<ResolvedFunction<'a>> {
+        let function_idx = self.source_location.function_idx;
+        if function_idx == u32::MAX {
+            return None;
+        }
+        self.converter
+            .functions
+            .get_index(function_idx as usize)
+            .map(|function| ResolvedFunction {
+                converter: self.converter,
+                function,
+            })
+    }
+
+    fn file(&self) -> Option<&'a File> {
+        self.converter
+            .files
+            .get_index(self.source_location.file_idx as usize)
+    }
+}
+
+/// A resolved function, borrowed from a [`Converter`].
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedFunction<'a> {
+    converter: &'a Converter,
+    function: &'a Function,
+}
+
+impl<'a> ResolvedFunction<'a> {
+    /// The function's name exactly as read from debug info, if known: its
+    /// `DW_AT_name`, or its (possibly mangled) `DW_AT_linkage_name` only when
+    /// it has no `DW_AT_name`. The linkage name of a function that has both
+    /// is not kept.
+    pub fn name(&self) -> Option<&'a str> {
+        self.function
+            .name_idx
+            .and_then(|idx| self.converter.strings.get_index(idx as usize))
+            .map(String::as_str)
+    }
+
+    /// The entry address of the function, if known.
+    pub fn entry_pc(&self) -> Option<u64> {
+        self.function.entry_pc
+    }
+
+    /// The source language the function was compiled from.
+    pub fn language(&self) -> Language {
+        self.function.language
+    }
+
+    /// The function's display name: demangled according to `options` when
+    /// its language is known to support it, falling back to the raw name
+    /// from [`Self::name`] when the language is `Unknown` or demangling
+    /// fails.
+    pub fn display_name(&self, options: DemangleOptions) -> Cow<'a, str> {
+        let name = match self.name() {
+            Some(name) => name,
+            None => return Cow::Borrowed(""),
+        };
+        if self.language() == Language::Unknown {
+            return Cow::Borrowed(name);
+        }
+        match demangle(name, self.language(), options) {
+            Some(demangled) => Cow::Owned(demangled),
+            None => Cow::Borrowed(name),
+        }
+    }
+}
+
+/// Iterates a chain of resolved source locations, innermost (possibly
+/// inlined) frame first, followed by each enclosing call site.
+#[derive(Debug, Clone)]
+pub struct SourceLocationIter<'a> {
+    converter: &'a Converter,
+    next: Option<u32>,
+}
+
+impl<'a> SourceLocationIter<'a> {
+    pub(super) fn new(converter: &'a Converter, start: Option<u32>) -> Self {
+        Self {
+            converter,
+            next: start,
+        }
+    }
+}
+
+impl<'a> Iterator for SourceLocationIter<'a> {
+    type Item = ResolvedLocation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.next.take()?;
+        let source_location = self.converter.source_locations.get_index(idx as usize)?;
+        self.next = source_location.inlined_into_idx;
+        Some(ResolvedLocation {
+            converter: self.converter,
+            source_location,
+        })
+    }
+}
+
+/// Iterates every mapped row within `[start, end)`, yielding the address it
+/// begins at together with its chain of resolved source locations.
+///
+/// Analogous to addr2line's `find_location_range`: a row that starts before
+/// `start` but whose covered span extends into `[start, end)` is included,
+/// clipped to `start`; the extent of each yielded row is implicitly bounded
+/// by the next row's start address, or by `end` for the last one.
+#[derive(Debug, Clone)]
+pub struct SourceLocationRangeIter<'a> {
+    converter: &'a Converter,
+    range: btree_map::Range<'a, u32, Option<u32>>,
+    start: u32,
+    end: u32,
+}
+
+impl<'a> Iterator for SourceLocationRangeIter<'a> {
+    type Item = (u32, SourceLocationIter<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (&addr, &idx) = self.range.next()?;
+            if addr >= self.end {
+                return None;
+            }
+            // Tombstones mark an unmapped gap; there's nothing to yield for
+            // them, so skip straight to the next row.
+            if let Some(idx) = idx {
+                let addr = addr.max(self.start);
+                return Some((addr, SourceLocationIter::new(self.converter, Some(idx))));
+            }
+        }
+    }
+}
+
+impl Converter {
+    /// Looks up the chain of source locations covering `addr`, if any.
+    ///
+    /// The returned iterator yields the innermost (possibly inlined) location
+    /// first, followed by each enclosing call site up to the physical
+    /// function.
+    pub fn lookup(&self, addr: u64) -> SourceLocationIter<'_> {
+        let start = self
+            .ranges
+            .range(..=(addr as u32))
+            .next_back()
+            .and_then(|(_, &idx)| idx);
+        SourceLocationIter::new(self, start)
+    }
+
+    /// Looks up every mapped row in `[start, end)`, e.g. to symbolicate an
+    /// entire function body or coverage interval in one pass instead of
+    /// issuing a point [`lookup`](Self::lookup) per address.
+    ///
+    /// An empty or inverted range (`end <= start`) yields no rows, matching
+    /// `BTreeMap::range`'s own convention for `start..start` while avoiding
+    /// its panic on `start > end`.
+    pub fn lookup_range(&self, start: u64, end: u64) -> SourceLocationRangeIter<'_> {
+        let start = start as u32;
+        let end = end as u32;
+        if end <= start {
+            return SourceLocationRangeIter {
+                converter: self,
+                range: self.ranges.range(start..start),
+                start,
+                end: start,
+            };
+        }
+        let first = self
+            .ranges
+            .range(..=start)
+            .next_back()
+            .map(|(&addr, _)| addr)
+            .unwrap_or(start);
+        SourceLocationRangeIter {
+            converter: self,
+            range: self.ranges.range(first..end),
+            start,
+            end,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::SourceLocation;
+
+    fn converter_with_rows(rows: &[(u32, Option<u32>)]) -> Converter {
+        let mut converter = Converter::new();
+        converter.files.insert(File {
+            directory_idx: None,
+            path_name_idx: converter.strings.insert_full("main.rs".into()).0 as u32,
+        });
+        for &(addr, line) in rows {
+            let idx = line.map(|line| {
+                converter.insert_source_location(SourceLocation {
+                    file_idx: 0,
+                    line,
+                    function_idx: u32::MAX,
+                    inlined_into_idx: None,
+                })
+            });
+            converter.ranges.insert(addr, idx);
+        }
+        converter
+    }
+
+    #[test]
+    fn lookup_range_with_start_past_end_yields_nothing() {
+        let converter = converter_with_rows(&[(0x10, Some(1)), (0x20, Some(2))]);
+        assert_eq!(converter.lookup_range(0x20, 0x10).count(), 0);
+    }
+
+    #[test]
+    fn lookup_range_with_equal_start_and_end_yields_nothing() {
+        let converter = converter_with_rows(&[(0x10, Some(1)), (0x20, Some(2))]);
+        assert_eq!(converter.lookup_range(0x10, 0x10).count(), 0);
+    }
+
+    #[test]
+    fn lookup_range_clips_a_row_that_starts_before_the_requested_range() {
+        let converter = converter_with_rows(&[(0x10, Some(1)), (0x20, Some(2)), (0x30, None)]);
+        let rows: Vec<_> = converter
+            .lookup_range(0x18, 0x30)
+            .map(|(addr, mut locations)| (addr, locations.next().unwrap().line()))
+            .collect();
+        assert_eq!(rows, vec![(0x18, 1), (0x20, 2)]);
+    }
+
+    fn converter_with_function(name: &str, language: Language) -> (Converter, u32) {
+        let mut converter = Converter::new();
+        converter.files.insert(File {
+            directory_idx: None,
+            path_name_idx: converter.strings.insert_full("main.rs".into()).0 as u32,
+        });
+        let name_idx = converter.strings.insert_full(name.into()).0 as u32;
+        let function_idx = converter
+            .functions
+            .insert_full(Function {
+                name_idx: Some(name_idx),
+                entry_pc: None,
+                language,
+            })
+            .0 as u32;
+        let source_idx = converter.insert_source_location(SourceLocation {
+            file_idx: 0,
+            line: 1,
+            function_idx,
+            inlined_into_idx: None,
+        });
+        (converter, source_idx)
+    }
+
+    #[test]
+    fn display_name_falls_back_to_raw_name_for_unknown_language() {
+        let (converter, source_idx) = converter_with_function("_ZN4core4iter", Language::Unknown);
+        let location = SourceLocationIter::new(&converter, Some(source_idx))
+            .next()
+            .unwrap();
+        let function = location.function().unwrap();
+        assert_eq!(
+            function.display_name(DemangleOptions::default()),
+            "_ZN4core4iter"
+        );
+    }
+
+    #[test]
+    fn display_name_falls_back_when_demangling_fails() {
+        // A known language doesn't guarantee a successfully mangled name;
+        // `display_name` must still fall back to the raw name rather than
+        // propagating a demangling failure.
+        let (converter, source_idx) =
+            converter_with_function("not a mangled symbol", Language::Cpp);
+        let location = SourceLocationIter::new(&converter, Some(source_idx))
+            .next()
+            .unwrap();
+        let function = location.function().unwrap();
+        assert_eq!(
+            function.display_name(DemangleOptions::default()),
+            "not a mangled symbol"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Converts debug information (DWARF, ...) from object files into a compact,
+//! serializable table that supports fast address-to-source-location lookups.
+
+use std::collections::BTreeMap;
+
+use indexmap::IndexSet;
+
+use symbolic_common::types::Language;
+
+mod dwarf;
+pub mod lookup;
+
+pub use self::dwarf::SplitDwarfLoader;
+
+/// Incrementally builds up the tables needed to resolve addresses to source
+/// locations.
+#[derive(Debug, Default)]
+pub struct Converter {
+    strings: IndexSet<String>,
+    files: IndexSet<File>,
+    functions: IndexSet<Function>,
+    source_locations: IndexSet<SourceLocation>,
+    // `None` marks an address as explicitly *unmapped*: the end of a line
+    // program sequence, beyond which a point lookup must not fall back to
+    // the preceding row.
+    ranges: BTreeMap<u32, Option<u32>>,
+}
+
+impl Converter {
+    /// Creates a new, empty converter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// A source file referenced by one or more [`SourceLocation`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct File {
+    directory_idx: Option<u32>,
+    path_name_idx: u32,
+}
+
+/// A function referenced by one or more [`SourceLocation`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Function {
+    name_idx: Option<u32>,
+    entry_pc: Option<u64>,
+    language: Language,
+}
+
+/// A resolved line-table entry, possibly one link in an inlined call chain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SourceLocation {
+    file_idx: u32,
+    line: u32,
+    function_idx: u32,
+    inlined_into_idx: Option<u32>,
+}